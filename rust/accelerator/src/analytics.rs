@@ -0,0 +1,111 @@
+//! Graph analytics methods built on top of `conversion`'s petgraph round-trip
+//!
+//! Gated behind the `petgraph` feature, same as `conversion`. These reuse
+//! petgraph's traversal and shortest-path algorithms instead of reimplementing
+//! them by hand.
+#![cfg(feature = "petgraph")]
+
+use crate::conversion;
+use crate::graph_formats;
+use crate::protocol::*;
+use petgraph::unionfind::UnionFind;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::collections::HashMap;
+
+pub fn compute_induced_subgraph(request: &Request) -> Result<InducedSubgraphResult, String> {
+    let params: InducedSubgraphParams = serde_json::from_value(request.params.clone())
+        .map_err(|e| format!("Invalid params: {}", e))?;
+    let (nodes, edges) = graph_formats::convert_graph(params.format, params.nodes, params.edges)?;
+
+    let sample = crate::sampling::random_sample(
+        &nodes,
+        &edges,
+        params.count,
+        params.weight_property.as_deref(),
+    );
+
+    // Round-trip through petgraph to prove the conversion is lossless, and hand back
+    // the node-index map so callers can address the sampled nodes by `NodeIndex`.
+    let (graph, index_map) = conversion::to_petgraph(&sample.sampled_nodes, &sample.sampled_edges);
+    let (sampled_nodes, sampled_edges) = conversion::from_petgraph(&graph);
+    let node_index = index_map.into_iter().map(|(id, idx)| (id, idx.index())).collect();
+
+    Ok(InducedSubgraphResult {
+        sample: SampleResult {
+            sampled_nodes,
+            sampled_edges,
+        },
+        node_index,
+    })
+}
+
+pub fn compute_connected_components(request: &Request) -> Result<ConnectedComponentsResult, String> {
+    let params: AnalyticsGraphParams = serde_json::from_value(request.params.clone())
+        .map_err(|e| format!("Invalid params: {}", e))?;
+    let (nodes, edges) = graph_formats::convert_graph(params.format, params.nodes, params.edges)?;
+    let (graph, index_map) = conversion::to_petgraph(&nodes, &edges);
+
+    let mut union_find = UnionFind::new(graph.node_count());
+    for edge in graph.edge_references() {
+        union_find.union(edge.source().index(), edge.target().index());
+    }
+
+    let mut component_ids: HashMap<usize, usize> = HashMap::new();
+    let mut components: HashMap<String, usize> = HashMap::with_capacity(index_map.len());
+
+    for (node_id, index) in &index_map {
+        let root = union_find.find(index.index());
+        let next_id = component_ids.len();
+        let component_id = *component_ids.entry(root).or_insert(next_id);
+        components.insert(node_id.clone(), component_id);
+    }
+
+    Ok(ConnectedComponentsResult {
+        component_count: component_ids.len(),
+        components,
+    })
+}
+
+pub fn compute_degree_histogram(request: &Request) -> Result<DegreeHistogramResult, String> {
+    let params: AnalyticsGraphParams = serde_json::from_value(request.params.clone())
+        .map_err(|e| format!("Invalid params: {}", e))?;
+    let (nodes, edges) = graph_formats::convert_graph(params.format, params.nodes, params.edges)?;
+    let (graph, _index_map) = conversion::to_petgraph(&nodes, &edges);
+
+    let mut histogram: HashMap<usize, usize> = HashMap::new();
+    for node in graph.node_indices() {
+        let degree = graph.edges_directed(node, Direction::Outgoing).count()
+            + graph.edges_directed(node, Direction::Incoming).count();
+        *histogram.entry(degree).or_insert(0) += 1;
+    }
+
+    Ok(DegreeHistogramResult { histogram })
+}
+
+pub fn compute_shortest_path(request: &Request) -> Result<ShortestPathResult, String> {
+    let params: ShortestPathParams = serde_json::from_value(request.params.clone())
+        .map_err(|e| format!("Invalid params: {}", e))?;
+    let (nodes, edges) = graph_formats::convert_graph(params.format, params.nodes, params.edges)?;
+    let (graph, index_map) = conversion::to_petgraph(&nodes, &edges);
+
+    let source = *index_map
+        .get(&params.source_id)
+        .ok_or_else(|| format!("Unknown sourceId: {}", params.source_id))?;
+    let target = *index_map
+        .get(&params.target_id)
+        .ok_or_else(|| format!("Unknown targetId: {}", params.target_id))?;
+
+    let found = petgraph::algo::astar(&graph, source, |node| node == target, |_| 1usize, |_| 0usize);
+
+    Ok(match found {
+        Some((distance, path)) => ShortestPathResult {
+            path: path.into_iter().map(|idx| graph[idx].id.clone()).collect(),
+            distance: Some(distance),
+        },
+        None => ShortestPathResult {
+            path: Vec::new(),
+            distance: None,
+        },
+    })
+}
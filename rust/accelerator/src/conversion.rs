@@ -0,0 +1,46 @@
+//! Conversions between this crate's graph model and `petgraph::Graph`
+//!
+//! Gated behind the `petgraph` feature so consumers who only need sampling
+//! aren't forced to pull in the dependency. This is the on-ramp for analytics
+//! methods that want petgraph's traversal/shortest-path algorithms instead of
+//! reimplementing them by hand.
+#![cfg(feature = "petgraph")]
+
+use crate::protocol::{GraphEdge, GraphNode};
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::Directed;
+use std::collections::HashMap;
+
+/// Maps native node ids to their index in a converted `petgraph::Graph`
+pub type NodeIndexMap = HashMap<String, NodeIndex>;
+
+/// Convert this crate's node/edge collections into a `petgraph::Graph`, preserving
+/// ids, labels, and properties on the node/edge weights. Returns the graph alongside
+/// a map from native node id to `NodeIndex`, since petgraph addresses nodes by index
+/// rather than by id.
+pub fn to_petgraph(nodes: &[GraphNode], edges: &[GraphEdge]) -> (Graph<GraphNode, GraphEdge, Directed>, NodeIndexMap) {
+    let mut graph = Graph::new();
+    let mut index_map = NodeIndexMap::with_capacity(nodes.len());
+
+    for node in nodes {
+        let index = graph.add_node(node.clone());
+        index_map.insert(node.id.clone(), index);
+    }
+
+    for edge in edges {
+        if let (Some(&source), Some(&target)) = (index_map.get(&edge.source), index_map.get(&edge.target)) {
+            graph.add_edge(source, target, edge.clone());
+        }
+    }
+
+    (graph, index_map)
+}
+
+/// Convert a `petgraph::Graph` back into this crate's node/edge collections; the
+/// inverse of `to_petgraph`. Node and edge weights round-trip losslessly since the
+/// graph's weights already are this crate's own `GraphNode`/`GraphEdge` types.
+pub fn from_petgraph(graph: &Graph<GraphNode, GraphEdge, Directed>) -> (Vec<GraphNode>, Vec<GraphEdge>) {
+    let nodes = graph.node_weights().cloned().collect();
+    let edges = graph.edge_weights().cloned().collect();
+    (nodes, edges)
+}
@@ -0,0 +1,146 @@
+//! JSON-RPC method dispatch, shared by the stdio and HTTP/WebSocket transports
+
+use crate::graph_formats;
+use crate::protocol::*;
+use crate::sampling;
+
+/// Handle a request and return the full serialized JSON-RPC response
+pub fn handle_request(request: &Request) -> Result<String, String> {
+    if let Some(result) = compute_sample_result(request) {
+        let result = result?;
+        let response = SuccessResponse::new(request.id, result);
+        return serde_json::to_string(&response).map_err(|e| e.to_string());
+    }
+
+    match request.method.as_str() {
+        "protocol.version" => {
+            let result = ProtocolVersionResult {
+                version: PROTOCOL_VERSION.to_string(),
+                features: FEATURES.iter().map(|s| s.to_string()).collect(),
+            };
+            let response = SuccessResponse::new(request.id, result);
+            serde_json::to_string(&response).map_err(|e| e.to_string())
+        }
+
+        "community.detect" => {
+            let result = compute_community_detection(request)?;
+            let response = SuccessResponse::new(request.id, result);
+            serde_json::to_string(&response).map_err(|e| e.to_string())
+        }
+
+        #[cfg(feature = "petgraph")]
+        "sample.inducedSubgraph" => {
+            let result = crate::analytics::compute_induced_subgraph(request)?;
+            let response = SuccessResponse::new(request.id, result);
+            serde_json::to_string(&response).map_err(|e| e.to_string())
+        }
+
+        #[cfg(feature = "petgraph")]
+        "analytics.connectedComponents" => {
+            let result = crate::analytics::compute_connected_components(request)?;
+            let response = SuccessResponse::new(request.id, result);
+            serde_json::to_string(&response).map_err(|e| e.to_string())
+        }
+
+        #[cfg(feature = "petgraph")]
+        "analytics.degreeHistogram" => {
+            let result = crate::analytics::compute_degree_histogram(request)?;
+            let response = SuccessResponse::new(request.id, result);
+            serde_json::to_string(&response).map_err(|e| e.to_string())
+        }
+
+        #[cfg(feature = "petgraph")]
+        "analytics.shortestPath" => {
+            let result = crate::analytics::compute_shortest_path(request)?;
+            let response = SuccessResponse::new(request.id, result);
+            serde_json::to_string(&response).map_err(|e| e.to_string())
+        }
+
+        _ => {
+            let response = ErrorResponse::method_not_found(request.id, &request.method);
+            serde_json::to_string(&response).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Compute a sample method's result directly, bypassing JSON serialization of the
+/// full response. Transports that can stream a `SampleResult` incrementally (rather
+/// than buffering the serialized response) use this instead of `handle_request`.
+/// Returns `None` if `request.method` isn't a sample method.
+pub fn compute_sample_result(request: &Request) -> Option<Result<SampleResult, String>> {
+    match request.method.as_str() {
+        "sample.random" => Some(compute_random_sample(request)),
+        "sample.randomWalk" => Some(compute_random_walk(request)),
+        "sample.biasedRandomWalk" => Some(compute_biased_random_walk(request)),
+        "sample.frontier" => Some(compute_frontier(request)),
+        _ => None,
+    }
+}
+
+fn compute_random_sample(request: &Request) -> Result<SampleResult, String> {
+    let params: RandomSampleParams = serde_json::from_value(request.params.clone())
+        .map_err(|e| format!("Invalid params: {}", e))?;
+    let (nodes, edges) = graph_formats::convert_graph(params.format, params.nodes, params.edges)?;
+
+    Ok(sampling::random_sample(
+        &nodes,
+        &edges,
+        params.count,
+        params.weight_property.as_deref(),
+    ))
+}
+
+fn compute_random_walk(request: &Request) -> Result<SampleResult, String> {
+    let params: RandomWalkParams = serde_json::from_value(request.params.clone())
+        .map_err(|e| format!("Invalid params: {}", e))?;
+    let (nodes, edges) = graph_formats::convert_graph(params.format, params.nodes, params.edges)?;
+
+    Ok(sampling::random_walk_sample(
+        &nodes,
+        &edges,
+        &params.start_node_id,
+        params.walk_length,
+        params.num_walks,
+        params.threads,
+    ))
+}
+
+fn compute_biased_random_walk(request: &Request) -> Result<SampleResult, String> {
+    let params: BiasedRandomWalkParams = serde_json::from_value(request.params.clone())
+        .map_err(|e| format!("Invalid params: {}", e))?;
+    let (nodes, edges) = graph_formats::convert_graph(params.format, params.nodes, params.edges)?;
+
+    Ok(sampling::biased_random_walk_sample(
+        &nodes,
+        &edges,
+        &params.start_node_id,
+        params.walk_length,
+        params.num_walks,
+        params.p,
+        params.q,
+        params.weight_property.as_deref(),
+    ))
+}
+
+fn compute_frontier(request: &Request) -> Result<SampleResult, String> {
+    let params: FrontierSampleParams = serde_json::from_value(request.params.clone())
+        .map_err(|e| format!("Invalid params: {}", e))?;
+    let (nodes, edges) = graph_formats::convert_graph(params.format, params.nodes, params.edges)?;
+
+    Ok(sampling::frontier_sample(
+        &nodes,
+        &edges,
+        &params.start_node_ids,
+        params.max_nodes,
+        params.weight_property.as_deref(),
+        params.threads,
+    ))
+}
+
+fn compute_community_detection(request: &Request) -> Result<CommunityDetectionResult, String> {
+    let params: CommunityDetectionParams = serde_json::from_value(request.params.clone())
+        .map_err(|e| format!("Invalid params: {}", e))?;
+    let (nodes, edges) = graph_formats::convert_graph(params.format, params.nodes, params.edges)?;
+
+    Ok(sampling::label_propagation(&nodes, &edges, params.max_iterations))
+}
@@ -0,0 +1,233 @@
+//! Adapters for ingesting foreign graph JSON schemas
+//!
+//! Every sample method ultimately needs `Vec<GraphNode>`/`Vec<GraphEdge>` in this
+//! crate's native shape. This module lets a request instead carry its graph in a
+//! different upstream export format, selected via the request's `format` field, and
+//! converts it to the native model before sampling runs.
+
+use crate::protocol::{GraphEdge, GraphNode};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Which JSON schema a sample request's `nodes`/`edges` payload is encoded in
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GraphFormat {
+    /// This crate's native `{ nodes: [...], edges: [...] }` shape
+    #[default]
+    Native,
+    /// Each node carries its own outgoing edges in an `adjacency` array
+    AdjacencyList,
+    /// A flat list of nodes alongside a flat list of edges
+    EdgeList,
+}
+
+/// Convert a request's raw `nodes`/`edges` JSON into the native graph model,
+/// according to `format`. `raw_edges` is ignored by formats (like adjacency-list)
+/// that embed their edges inside `raw_nodes`.
+pub fn convert_graph(
+    format: GraphFormat,
+    raw_nodes: serde_json::Value,
+    raw_edges: serde_json::Value,
+) -> Result<(Vec<GraphNode>, Vec<GraphEdge>), String> {
+    match format {
+        GraphFormat::Native => {
+            let nodes: Vec<GraphNode> =
+                serde_json::from_value(raw_nodes).map_err(|e| format!("Invalid nodes: {}", e))?;
+            let edges: Vec<GraphEdge> =
+                serde_json::from_value(raw_edges).map_err(|e| format!("Invalid edges: {}", e))?;
+            Ok((nodes, edges))
+        }
+        GraphFormat::AdjacencyList => convert_adjacency_list(raw_nodes),
+        GraphFormat::EdgeList => convert_edge_list(raw_nodes, raw_edges),
+    }
+}
+
+/// A node or edge id that may arrive as either a JSON string or a JSON number
+fn flexible_id<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum IdValue {
+        String(String),
+        Number(serde_json::Number),
+    }
+
+    match IdValue::deserialize(deserializer)? {
+        IdValue::String(s) => Ok(s),
+        IdValue::Number(n) => Ok(n.to_string()),
+    }
+}
+
+fn flexible_id_opt<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum IdValue {
+        String(String),
+        Number(serde_json::Number),
+    }
+
+    Ok(Option::<IdValue>::deserialize(deserializer)?.map(|v| match v {
+        IdValue::String(s) => s,
+        IdValue::Number(n) => n.to_string(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct AdjacencyNode {
+    #[serde(alias = "nodeId", deserialize_with = "flexible_id")]
+    id: String,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    properties: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    adjacency: Vec<AdjacencyEdge>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdjacencyEdge {
+    #[serde(default, deserialize_with = "flexible_id_opt")]
+    id: Option<String>,
+    #[serde(alias = "to", alias = "dst", deserialize_with = "flexible_id")]
+    target: String,
+    #[serde(default, rename = "type", alias = "label")]
+    edge_type: Option<String>,
+    #[serde(default)]
+    properties: HashMap<String, serde_json::Value>,
+}
+
+fn convert_adjacency_list(raw_nodes: serde_json::Value) -> Result<(Vec<GraphNode>, Vec<GraphEdge>), String> {
+    let adjacency_nodes: Vec<AdjacencyNode> =
+        serde_json::from_value(raw_nodes).map_err(|e| format!("Invalid adjacency-list nodes: {}", e))?;
+
+    let mut nodes = Vec::with_capacity(adjacency_nodes.len());
+    let mut edges = Vec::new();
+
+    for node in adjacency_nodes {
+        for (i, edge) in node.adjacency.into_iter().enumerate() {
+            edges.push(GraphEdge {
+                id: edge.id.unwrap_or_else(|| format!("{}->{}#{}", node.id, edge.target, i)),
+                source: node.id.clone(),
+                target: edge.target,
+                edge_type: edge.edge_type.unwrap_or_else(|| "CONNECTS".to_string()),
+                properties: edge.properties,
+            });
+        }
+
+        nodes.push(GraphNode {
+            id: node.id,
+            labels: node.labels,
+            properties: node.properties,
+        });
+    }
+
+    Ok((nodes, edges))
+}
+
+#[derive(Debug, Deserialize)]
+struct FlatNode {
+    #[serde(deserialize_with = "flexible_id")]
+    id: String,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    properties: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FlatEdge {
+    #[serde(default, deserialize_with = "flexible_id_opt")]
+    id: Option<String>,
+    #[serde(alias = "src", alias = "from", deserialize_with = "flexible_id")]
+    source: String,
+    #[serde(alias = "dst", alias = "to", deserialize_with = "flexible_id")]
+    target: String,
+    #[serde(default, rename = "type", alias = "label")]
+    edge_type: Option<String>,
+    #[serde(default)]
+    properties: HashMap<String, serde_json::Value>,
+}
+
+fn convert_edge_list(
+    raw_nodes: serde_json::Value,
+    raw_edges: serde_json::Value,
+) -> Result<(Vec<GraphNode>, Vec<GraphEdge>), String> {
+    let flat_nodes: Vec<FlatNode> =
+        serde_json::from_value(raw_nodes).map_err(|e| format!("Invalid edge-list nodes: {}", e))?;
+    let flat_edges: Vec<FlatEdge> =
+        serde_json::from_value(raw_edges).map_err(|e| format!("Invalid edge-list edges: {}", e))?;
+
+    let nodes = flat_nodes
+        .into_iter()
+        .map(|n| GraphNode {
+            id: n.id,
+            labels: n.labels,
+            properties: n.properties,
+        })
+        .collect();
+
+    let edges = flat_edges
+        .into_iter()
+        .enumerate()
+        .map(|(i, e)| GraphEdge {
+            id: e.id.unwrap_or_else(|| format!("{}->{}#{}", e.source, e.target, i)),
+            source: e.source,
+            target: e.target,
+            edge_type: e.edge_type.unwrap_or_else(|| "CONNECTS".to_string()),
+            properties: e.properties,
+        })
+        .collect();
+
+    Ok((nodes, edges))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_adjacency_list() {
+        let raw_nodes = serde_json::json!([
+            {"id": 1, "labels": ["Node"], "adjacency": [{"to": 2, "weight": 0.5}]},
+            {"id": 2, "labels": ["Node"], "adjacency": []},
+        ]);
+
+        let (nodes, edges) = convert_graph(GraphFormat::AdjacencyList, raw_nodes, serde_json::Value::Null).unwrap();
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].source, "1");
+        assert_eq!(edges[0].target, "2");
+    }
+
+    #[test]
+    fn test_convert_edge_list() {
+        let raw_nodes = serde_json::json!([{"id": "a"}, {"id": "b"}]);
+        let raw_edges = serde_json::json!([{"src": "a", "dst": "b", "type": "FOLLOWS"}]);
+
+        let (nodes, edges) = convert_graph(GraphFormat::EdgeList, raw_nodes, raw_edges).unwrap();
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].source, "a");
+        assert_eq!(edges[0].target, "b");
+        assert_eq!(edges[0].edge_type, "FOLLOWS");
+    }
+
+    #[test]
+    fn test_convert_native() {
+        let raw_nodes = serde_json::json!([{"id": "n0", "labels": [], "properties": {}}]);
+        let raw_edges = serde_json::json!([]);
+
+        let (nodes, edges) = convert_graph(GraphFormat::Native, raw_nodes, raw_edges).unwrap();
+
+        assert_eq!(nodes.len(), 1);
+        assert!(edges.is_empty());
+    }
+}
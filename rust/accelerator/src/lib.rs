@@ -1,7 +1,14 @@
 //! GraphScope Accelerator Library
 
+#[cfg(feature = "petgraph")]
+pub mod analytics;
+#[cfg(feature = "petgraph")]
+pub mod conversion;
+pub mod dispatch;
+pub mod graph_formats;
 pub mod protocol;
 pub mod sampling;
+pub mod transport;
 
 pub use protocol::*;
 pub use sampling::*;
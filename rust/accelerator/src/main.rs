@@ -1,69 +1,37 @@
 //! GraphScope Accelerator Sidecar
 //!
-//! A JSON-RPC server that provides accelerated graph operations.
-//! Communicates over stdin/stdout for easy integration with Node.js.
+//! A JSON-RPC server that provides accelerated graph operations. By default
+//! it communicates over stdin/stdout for easy integration with Node.js; pass
+//! `--listen <addr>` to instead expose the same dispatch over HTTP/WebSocket.
 
+use graphscope_accelerator::dispatch::handle_request;
 use graphscope_accelerator::protocol::*;
-use graphscope_accelerator::sampling;
+use graphscope_accelerator::transport;
 use std::io::{self, BufRead, Write};
+use std::net::SocketAddr;
 
-fn handle_request(request: &Request) -> Result<String, String> {
-    match request.method.as_str() {
-        "protocol.version" => {
-            let result = ProtocolVersionResult {
-                version: PROTOCOL_VERSION.to_string(),
-                features: FEATURES.iter().map(|s| s.to_string()).collect(),
-            };
-            let response = SuccessResponse::new(request.id, result);
-            serde_json::to_string(&response).map_err(|e| e.to_string())
-        }
-
-        "sample.random" => {
-            let params: RandomSampleParams = serde_json::from_value(request.params.clone())
-                .map_err(|e| format!("Invalid params: {}", e))?;
-
-            let result = sampling::random_sample(&params.nodes, &params.edges, params.count);
-            let response = SuccessResponse::new(request.id, result);
-            serde_json::to_string(&response).map_err(|e| e.to_string())
-        }
-
-        "sample.randomWalk" => {
-            let params: RandomWalkParams = serde_json::from_value(request.params.clone())
-                .map_err(|e| format!("Invalid params: {}", e))?;
-
-            let result = sampling::random_walk_sample(
-                &params.nodes,
-                &params.edges,
-                &params.start_node_id,
-                params.walk_length,
-                params.num_walks,
-            );
-            let response = SuccessResponse::new(request.id, result);
-            serde_json::to_string(&response).map_err(|e| e.to_string())
-        }
-
-        "sample.frontier" => {
-            let params: FrontierSampleParams = serde_json::from_value(request.params.clone())
-                .map_err(|e| format!("Invalid params: {}", e))?;
-
-            let result = sampling::frontier_sample(
-                &params.nodes,
-                &params.edges,
-                &params.start_node_ids,
-                params.max_nodes,
-            );
-            let response = SuccessResponse::new(request.id, result);
-            serde_json::to_string(&response).map_err(|e| e.to_string())
+fn main() {
+    match parse_listen_flag(std::env::args()) {
+        Some(addr) => {
+            let runtime = tokio::runtime::Runtime::new().expect("failed to start async runtime");
+            runtime.block_on(transport::serve(addr));
         }
+        None => run_stdio(),
+    }
+}
 
-        _ => {
-            let response = ErrorResponse::method_not_found(request.id, &request.method);
-            serde_json::to_string(&response).map_err(|e| e.to_string())
+/// Parse a `--listen <addr>` flag out of the CLI args, if present
+fn parse_listen_flag(mut args: impl Iterator<Item = String>) -> Option<SocketAddr> {
+    while let Some(arg) = args.next() {
+        if arg == "--listen" {
+            return args.next().and_then(|v| v.parse().ok());
         }
     }
+
+    None
 }
 
-fn main() {
+fn run_stdio() {
     let stdin = io::stdin();
     let mut stdout = io::stdout();
 
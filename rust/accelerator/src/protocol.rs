@@ -1,5 +1,6 @@
 //! JSON-RPC protocol types
 
+use crate::graph_formats::GraphFormat;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -79,36 +80,205 @@ pub struct ProtocolVersionResult {
     pub features: Vec<String>,
 }
 
+/// Induced-subgraph sample params (see `analytics::compute_induced_subgraph`)
+#[cfg(feature = "petgraph")]
+#[derive(Debug, Deserialize)]
+pub struct InducedSubgraphParams {
+    pub nodes: serde_json::Value,
+    #[serde(default)]
+    pub edges: serde_json::Value,
+    #[serde(default)]
+    pub format: GraphFormat,
+    pub count: usize,
+    #[serde(rename = "weightProperty")]
+    pub weight_property: Option<String>,
+}
+
+/// Induced-subgraph sample result: a `SampleResult` round-tripped through petgraph, plus the
+/// node-index map callers need to address the sample by `petgraph::NodeIndex`
+#[cfg(feature = "petgraph")]
+#[derive(Debug, Serialize)]
+pub struct InducedSubgraphResult {
+    #[serde(flatten)]
+    pub sample: SampleResult,
+    /// Node id -> its index in the petgraph graph built for this sample
+    #[serde(rename = "nodeIndex")]
+    pub node_index: HashMap<String, usize>,
+}
+
+/// Params shared by the petgraph-backed analytics methods that just need a graph
+#[cfg(feature = "petgraph")]
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsGraphParams {
+    pub nodes: serde_json::Value,
+    #[serde(default)]
+    pub edges: serde_json::Value,
+    #[serde(default)]
+    pub format: GraphFormat,
+}
+
+/// Connected-components result
+#[cfg(feature = "petgraph")]
+#[derive(Debug, Serialize)]
+pub struct ConnectedComponentsResult {
+    #[serde(rename = "componentCount")]
+    pub component_count: usize,
+    /// Node id -> component id
+    pub components: HashMap<String, usize>,
+}
+
+/// Degree-histogram result
+#[cfg(feature = "petgraph")]
+#[derive(Debug, Serialize)]
+pub struct DegreeHistogramResult {
+    /// Degree -> number of nodes with that degree
+    pub histogram: HashMap<usize, usize>,
+}
+
+/// Shortest-path params
+#[cfg(feature = "petgraph")]
+#[derive(Debug, Deserialize)]
+pub struct ShortestPathParams {
+    pub nodes: serde_json::Value,
+    #[serde(default)]
+    pub edges: serde_json::Value,
+    #[serde(default)]
+    pub format: GraphFormat,
+    #[serde(rename = "sourceId")]
+    pub source_id: String,
+    #[serde(rename = "targetId")]
+    pub target_id: String,
+}
+
+/// Shortest-path result
+#[cfg(feature = "petgraph")]
+#[derive(Debug, Serialize)]
+pub struct ShortestPathResult {
+    /// Node ids from source to target, inclusive; empty if unreachable
+    pub path: Vec<String>,
+    /// Total edge-count distance; `None` if unreachable
+    pub distance: Option<usize>,
+}
+
+/// Community detection result
+#[derive(Debug, Serialize)]
+pub struct CommunityDetectionResult {
+    /// Node id -> final community id
+    pub communities: HashMap<String, usize>,
+    #[serde(rename = "communityCount")]
+    pub community_count: usize,
+}
+
+/// Community detection params
+#[derive(Debug, Deserialize)]
+pub struct CommunityDetectionParams {
+    pub nodes: serde_json::Value,
+    #[serde(default)]
+    pub edges: serde_json::Value,
+    /// Schema the `nodes`/`edges` payload is encoded in; defaults to the native shape
+    #[serde(default)]
+    pub format: GraphFormat,
+    /// Upper bound on label-propagation passes
+    #[serde(rename = "maxIterations", default = "default_max_iterations")]
+    pub max_iterations: usize,
+}
+
+fn default_max_iterations() -> usize {
+    20
+}
+
 /// Random sample params
 #[derive(Debug, Deserialize)]
 pub struct RandomSampleParams {
-    pub nodes: Vec<GraphNode>,
-    pub edges: Vec<GraphEdge>,
+    pub nodes: serde_json::Value,
+    #[serde(default)]
+    pub edges: serde_json::Value,
+    /// Schema the `nodes`/`edges` payload is encoded in; defaults to the native shape
+    #[serde(default)]
+    pub format: GraphFormat,
     pub count: usize,
+    /// Node property to sample proportionally to; omit for uniform sampling
+    #[serde(rename = "weightProperty")]
+    pub weight_property: Option<String>,
 }
 
 /// Random walk params
 #[derive(Debug, Deserialize)]
 pub struct RandomWalkParams {
-    pub nodes: Vec<GraphNode>,
-    pub edges: Vec<GraphEdge>,
+    pub nodes: serde_json::Value,
+    #[serde(default)]
+    pub edges: serde_json::Value,
+    /// Schema the `nodes`/`edges` payload is encoded in; defaults to the native shape
+    #[serde(default)]
+    pub format: GraphFormat,
+    #[serde(rename = "startNodeId")]
+    pub start_node_id: String,
+    #[serde(rename = "walkLength")]
+    pub walk_length: usize,
+    #[serde(rename = "numWalks")]
+    pub num_walks: usize,
+    /// Worker threads to spread walks across; 0 auto-detects via available cores,
+    /// 1 keeps the walks single-threaded. Walks draw from an unseeded RNG, so the
+    /// sample differs from run to run at any thread count.
+    #[serde(default)]
+    pub threads: usize,
+}
+
+/// Biased (node2vec-style) random walk params
+#[derive(Debug, Deserialize)]
+pub struct BiasedRandomWalkParams {
+    pub nodes: serde_json::Value,
+    #[serde(default)]
+    pub edges: serde_json::Value,
+    /// Schema the `nodes`/`edges` payload is encoded in; defaults to the native shape
+    #[serde(default)]
+    pub format: GraphFormat,
     #[serde(rename = "startNodeId")]
     pub start_node_id: String,
     #[serde(rename = "walkLength")]
     pub walk_length: usize,
     #[serde(rename = "numWalks")]
     pub num_walks: usize,
+    /// Return parameter: lower values bias the walk towards revisiting the previous node
+    #[serde(default = "default_p")]
+    pub p: f64,
+    /// In-out parameter: lower values bias the walk towards exploring farther nodes
+    #[serde(default = "default_q")]
+    pub q: f64,
+    /// Edge property holding the edge weight; defaults to uniform weight of 1.0
+    #[serde(rename = "weightProperty")]
+    pub weight_property: Option<String>,
+}
+
+fn default_p() -> f64 {
+    1.0
+}
+
+fn default_q() -> f64 {
+    1.0
 }
 
 /// Frontier sample params
 #[derive(Debug, Deserialize)]
 pub struct FrontierSampleParams {
-    pub nodes: Vec<GraphNode>,
-    pub edges: Vec<GraphEdge>,
+    pub nodes: serde_json::Value,
+    #[serde(default)]
+    pub edges: serde_json::Value,
+    /// Schema the `nodes`/`edges` payload is encoded in; defaults to the native shape
+    #[serde(default)]
+    pub format: GraphFormat,
     #[serde(rename = "startNodeIds")]
     pub start_node_ids: Vec<String>,
     #[serde(rename = "maxNodes")]
     pub max_nodes: usize,
+    /// Node property used to prioritize which neighbors are enqueued first when the
+    /// frontier exceeds the remaining budget; omit to keep adjacency order
+    #[serde(rename = "weightProperty")]
+    pub weight_property: Option<String>,
+    /// Worker threads to spread disconnected start-node groups across; 0 auto-detects
+    /// via available cores, 1 keeps expansion single-threaded (useful for deterministic tests)
+    #[serde(default)]
+    pub threads: usize,
 }
 
 impl<T: Serialize> SuccessResponse<T> {
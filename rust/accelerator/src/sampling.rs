@@ -1,8 +1,36 @@
 //! Graph sampling algorithms
 
-use crate::protocol::{GraphEdge, GraphNode, SampleResult};
+use crate::protocol::{CommunityDetectionResult, GraphEdge, GraphNode, SampleResult};
 use rand::prelude::*;
-use std::collections::{HashMap, HashSet, VecDeque};
+use rayon::prelude::*;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+/// Wraps an `f64` so it can be used as a `BinaryHeap` key; NaNs sort as equal
+#[derive(PartialEq)]
+struct OrdF64(f64);
+
+impl Eq for OrdF64 {}
+
+impl PartialOrd for OrdF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl Ord for OrdF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Unnormalized node weight from the configured property, defaulting to 1.0
+fn node_weight(node: &GraphNode, weight_property: &str) -> f64 {
+    node.properties
+        .get(weight_property)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(1.0)
+}
 
 /// Build adjacency list from edges
 fn build_adjacency(edges: &[GraphEdge]) -> HashMap<String, Vec<&GraphEdge>> {
@@ -21,22 +49,47 @@ fn build_adjacency(edges: &[GraphEdge]) -> HashMap<String, Vec<&GraphEdge>> {
 }
 
 /// Random sample: select random nodes and include edges between them
-pub fn random_sample(nodes: &[GraphNode], edges: &[GraphEdge], count: usize) -> SampleResult {
+pub fn random_sample(
+    nodes: &[GraphNode],
+    edges: &[GraphEdge],
+    count: usize,
+    weight_property: Option<&str>,
+) -> SampleResult {
     let mut rng = rand::thread_rng();
-
-    // Fisher-Yates shuffle to select random nodes
     let count = count.min(nodes.len());
-    let mut indices: Vec<usize> = (0..nodes.len()).collect();
 
-    for i in (1..indices.len()).rev() {
-        let j = rng.gen_range(0..=i);
-        indices.swap(i, j);
-    }
+    let sampled_nodes: Vec<GraphNode> = if let Some(weight_key) = weight_property {
+        // Weighted reservoir sampling without replacement: keep the `count` nodes
+        // with the largest key k_i = u_i^(1/w_i) using a bounded min-heap.
+        let mut heap: BinaryHeap<Reverse<(OrdF64, usize)>> = BinaryHeap::with_capacity(count + 1);
+
+        for (i, node) in nodes.iter().enumerate() {
+            let weight = node_weight(node, weight_key).max(f64::MIN_POSITIVE);
+            let u: f64 = rng.gen_range(f64::MIN_POSITIVE..1.0);
+            let key = u.powf(1.0 / weight);
+
+            if heap.len() < count {
+                heap.push(Reverse((OrdF64(key), i)));
+            } else if let Some(Reverse((OrdF64(min_key), _))) = heap.peek() {
+                if key > *min_key {
+                    heap.pop();
+                    heap.push(Reverse((OrdF64(key), i)));
+                }
+            }
+        }
 
-    let sampled_nodes: Vec<GraphNode> = indices[..count]
-        .iter()
-        .map(|&i| nodes[i].clone())
-        .collect();
+        heap.into_iter().map(|Reverse((_, i))| nodes[i].clone()).collect()
+    } else {
+        // Fisher-Yates shuffle to select random nodes
+        let mut indices: Vec<usize> = (0..nodes.len()).collect();
+
+        for i in (1..indices.len()).rev() {
+            let j = rng.gen_range(0..=i);
+            indices.swap(i, j);
+        }
+
+        indices[..count].iter().map(|&i| nodes[i].clone()).collect()
+    };
 
     let sampled_ids: HashSet<&str> = sampled_nodes.iter().map(|n| n.id.as_str()).collect();
 
@@ -60,38 +113,191 @@ pub fn random_walk_sample(
     start_node_id: &str,
     walk_length: usize,
     num_walks: usize,
+    threads: usize,
+) -> SampleResult {
+    let adjacency = build_adjacency(edges);
+    let node_map: HashMap<&str, &GraphNode> = nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+
+    let pool = build_worker_pool(threads, num_walks);
+
+    // Each worker accumulates into its own thread-local sets; the only
+    // synchronization point is the final reduce merging them together.
+    let (visited_nodes, visited_edges) = pool.install(|| {
+        (0..num_walks)
+            .into_par_iter()
+            .map(|_| run_single_walk(&adjacency, start_node_id, walk_length))
+            .reduce(
+                || (HashSet::new(), HashSet::new()),
+                |mut acc, (nodes, edges)| {
+                    acc.0.extend(nodes);
+                    acc.1.extend(edges);
+                    acc
+                },
+            )
+    });
+
+    let sampled_nodes: Vec<GraphNode> = visited_nodes
+        .iter()
+        .filter_map(|id| node_map.get(id.as_str()).map(|&n| n.clone()))
+        .collect();
+
+    let sampled_edges: Vec<GraphEdge> = edges
+        .iter()
+        .filter(|e| visited_edges.contains(&e.id))
+        .cloned()
+        .collect();
+
+    SampleResult {
+        sampled_nodes,
+        sampled_edges,
+    }
+}
+
+/// Build a thread pool sized by `threads` (0 = auto-detect via `num_cpus`), capped at
+/// `work_items` so we never spin up more workers than there's work to hand them.
+fn build_worker_pool(threads: usize, work_items: usize) -> rayon::ThreadPool {
+    let threads = if threads == 0 { num_cpus::get() } else { threads };
+    let threads = threads.max(1).min(work_items.max(1));
+
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .expect("failed to build sampling worker pool")
+}
+
+/// Run a single random walk from `start_node_id`, returning the nodes and edges it visited
+fn run_single_walk(
+    adjacency: &HashMap<String, Vec<&GraphEdge>>,
+    start_node_id: &str,
+    walk_length: usize,
+) -> (HashSet<String>, HashSet<String>) {
+    let mut rng = rand::thread_rng();
+    let mut visited_nodes: HashSet<String> = HashSet::new();
+    let mut visited_edges: HashSet<String> = HashSet::new();
+
+    let mut current_node = start_node_id.to_string();
+    visited_nodes.insert(current_node.clone());
+
+    for _ in 0..walk_length {
+        let neighbors = match adjacency.get(&current_node) {
+            Some(n) if !n.is_empty() => n,
+            _ => break,
+        };
+
+        let edge = neighbors[rng.gen_range(0..neighbors.len())];
+        visited_edges.insert(edge.id.clone());
+
+        current_node = other_endpoint(edge, &current_node).to_string();
+        visited_nodes.insert(current_node.clone());
+    }
+
+    (visited_nodes, visited_edges)
+}
+
+/// Build a map from node id to the set of its neighbor ids, for O(1) adjacency checks
+fn build_neighbor_sets(adjacency: &HashMap<String, Vec<&GraphEdge>>) -> HashMap<String, HashSet<String>> {
+    adjacency
+        .iter()
+        .map(|(node, neighbor_edges)| {
+            let neighbors = neighbor_edges
+                .iter()
+                .map(|edge| other_endpoint(edge, node).to_string())
+                .collect();
+            (node.clone(), neighbors)
+        })
+        .collect()
+}
+
+/// The endpoint of `edge` that isn't `node`
+fn other_endpoint<'a>(edge: &'a GraphEdge, node: &str) -> &'a str {
+    if edge.source == node {
+        &edge.target
+    } else {
+        &edge.source
+    }
+}
+
+/// Unnormalized edge weight from the configured property, defaulting to 1.0
+fn edge_weight(edge: &GraphEdge, weight_property: Option<&str>) -> f64 {
+    weight_property
+        .and_then(|key| edge.properties.get(key))
+        .and_then(|v| v.as_f64())
+        .unwrap_or(1.0)
+}
+
+/// Biased (node2vec-style) second-order random walk sample
+#[allow(clippy::too_many_arguments)]
+pub fn biased_random_walk_sample(
+    nodes: &[GraphNode],
+    edges: &[GraphEdge],
+    start_node_id: &str,
+    walk_length: usize,
+    num_walks: usize,
+    p: f64,
+    q: f64,
+    weight_property: Option<&str>,
 ) -> SampleResult {
     let mut rng = rand::thread_rng();
     let adjacency = build_adjacency(edges);
+    let neighbor_sets = build_neighbor_sets(&adjacency);
     let node_map: HashMap<&str, &GraphNode> = nodes.iter().map(|n| (n.id.as_str(), n)).collect();
 
     let mut visited_nodes: HashSet<String> = HashSet::new();
     let mut visited_edges: HashSet<String> = HashSet::new();
 
     for _ in 0..num_walks {
+        let mut prev_node: Option<String> = None;
         let mut current_node = start_node_id.to_string();
         visited_nodes.insert(current_node.clone());
 
         for _ in 0..walk_length {
-            if let Some(neighbors) = adjacency.get(&current_node) {
-                if neighbors.is_empty() {
-                    break;
+            let neighbors = match adjacency.get(&current_node) {
+                Some(n) if !n.is_empty() => n,
+                _ => break,
+            };
+
+            let prev_neighbors = prev_node.as_ref().and_then(|t| neighbor_sets.get(t));
+
+            let weights: Vec<f64> = neighbors
+                .iter()
+                .map(|edge| {
+                    let next = other_endpoint(edge, &current_node);
+                    let alpha = match &prev_node {
+                        None => 1.0,
+                        Some(t) if next == t => 1.0 / p,
+                        Some(_) if prev_neighbors.map_or(false, |s| s.contains(next)) => 1.0,
+                        Some(_) => 1.0 / q,
+                    };
+                    (edge_weight(edge, weight_property) * alpha).max(0.0)
+                })
+                .collect();
+
+            let total: f64 = weights.iter().sum();
+            // A zero-weight property (or an all-repelled neighborhood) can make every
+            // candidate weight 0.0; `gen_range` panics on an empty range, so fall back
+            // to uniform selection instead of biased sampling in that case.
+            let chosen = if total > 0.0 {
+                let mut pick = rng.gen_range(0.0..total);
+                let mut chosen = neighbors.len() - 1;
+                for (i, w) in weights.iter().enumerate() {
+                    pick -= w;
+                    if pick <= 0.0 {
+                        chosen = i;
+                        break;
+                    }
                 }
+                chosen
+            } else {
+                rng.gen_range(0..neighbors.len())
+            };
 
-                let edge = neighbors[rng.gen_range(0..neighbors.len())];
-                visited_edges.insert(edge.id.clone());
-
-                // Move to the other endpoint
-                current_node = if edge.source == current_node {
-                    edge.target.clone()
-                } else {
-                    edge.source.clone()
-                };
+            let edge = neighbors[chosen];
+            visited_edges.insert(edge.id.clone());
 
-                visited_nodes.insert(current_node.clone());
-            } else {
-                break;
-            }
+            let next_node = other_endpoint(edge, &current_node).to_string();
+            prev_node = Some(current_node);
+            current_node = next_node;
+            visited_nodes.insert(current_node.clone());
         }
     }
 
@@ -113,15 +319,89 @@ pub fn random_walk_sample(
 }
 
 /// Frontier sample: BFS from start nodes up to max nodes
+#[allow(clippy::too_many_arguments)]
 pub fn frontier_sample(
     nodes: &[GraphNode],
     edges: &[GraphEdge],
     start_node_ids: &[String],
     max_nodes: usize,
+    weight_property: Option<&str>,
+    threads: usize,
 ) -> SampleResult {
     let adjacency = build_adjacency(edges);
     let node_map: HashMap<&str, &GraphNode> = nodes.iter().map(|n| (n.id.as_str(), n)).collect();
 
+    // With several start nodes, each one runs its own independent BFS on the worker
+    // pool (whether or not the start nodes are connected); a single start node runs
+    // the BFS directly on this thread.
+    let (visited_nodes, visited_edges) = if start_node_ids.len() > 1 {
+        let pool = build_worker_pool(threads, start_node_ids.len());
+
+        pool.install(|| {
+            start_node_ids
+                .par_iter()
+                .map(|start_node_id| {
+                    run_frontier_group(&adjacency, &node_map, std::slice::from_ref(start_node_id), max_nodes, weight_property)
+                })
+                .reduce(
+                    || (HashSet::new(), HashSet::new()),
+                    |mut acc, (nodes, edges)| {
+                        acc.0.extend(nodes);
+                        acc.1.extend(edges);
+                        acc
+                    },
+                )
+        })
+    } else {
+        run_frontier_group(&adjacency, &node_map, start_node_ids, max_nodes, weight_property)
+    };
+
+    // Independent groups can jointly overshoot the budget; trim back down to it
+    // deterministically (HashSet iteration order is randomly seeded per-process, so
+    // `take` on it would drop an arbitrary subset, potentially even a seed node).
+    // Seeds are always kept first, then the rest break ties by id.
+    let seed_ids: HashSet<&str> = start_node_ids.iter().map(String::as_str).collect();
+    let mut visited_nodes: Vec<String> = visited_nodes.into_iter().collect();
+    visited_nodes.sort_by(|a, b| {
+        let a_is_seed = seed_ids.contains(a.as_str());
+        let b_is_seed = seed_ids.contains(b.as_str());
+        b_is_seed.cmp(&a_is_seed).then_with(|| a.cmp(b))
+    });
+    visited_nodes.truncate(max_nodes);
+    let visited_nodes: HashSet<String> = visited_nodes.into_iter().collect();
+
+    let sampled_nodes: Vec<GraphNode> = visited_nodes
+        .iter()
+        .filter_map(|id| node_map.get(id.as_str()).map(|&n| n.clone()))
+        .collect();
+
+    // Drop any edge whose endpoint got trimmed above; otherwise the trim can leave
+    // `sampled_edges` referencing node ids no longer present in `sampled_nodes`.
+    let sampled_edges: Vec<GraphEdge> = edges
+        .iter()
+        .filter(|e| {
+            visited_edges.contains(&e.id)
+                && visited_nodes.contains(&e.source)
+                && visited_nodes.contains(&e.target)
+        })
+        .cloned()
+        .collect();
+
+    SampleResult {
+        sampled_nodes,
+        sampled_edges,
+    }
+}
+
+/// BFS-expand a single group of start nodes up to `max_nodes`, returning the visited node ids
+/// and edge ids. Shared by the single-threaded path and each worker in the parallel path.
+fn run_frontier_group(
+    adjacency: &HashMap<String, Vec<&GraphEdge>>,
+    node_map: &HashMap<&str, &GraphNode>,
+    start_node_ids: &[String],
+    max_nodes: usize,
+    weight_property: Option<&str>,
+) -> (HashSet<String>, HashSet<String>) {
     let mut visited_nodes: HashSet<String> = HashSet::new();
     let mut visited_edges: HashSet<String> = HashSet::new();
     let mut queue: VecDeque<String> = start_node_ids.iter().cloned().collect();
@@ -138,35 +418,101 @@ pub fn frontier_sample(
         visited_nodes.insert(current_node.clone());
 
         if let Some(neighbors) = adjacency.get(&current_node) {
-            for edge in neighbors {
-                let neighbor = if edge.source == current_node {
-                    &edge.target
-                } else {
-                    &edge.source
-                };
+            let mut candidates: Vec<&GraphEdge> = neighbors.iter().copied().collect();
+            let remaining_budget = max_nodes.saturating_sub(visited_nodes.len());
+
+            // Once the frontier outgrows the remaining budget, prioritize the
+            // highest-weight neighbors instead of taking them in adjacency order.
+            if let Some(weight_key) = weight_property {
+                if candidates.len() > remaining_budget {
+                    candidates.sort_by(|a, b| {
+                        let neighbor_a = other_endpoint(a, &current_node);
+                        let neighbor_b = other_endpoint(b, &current_node);
+                        let weight_a = node_map.get(neighbor_a).map_or(1.0, |n| node_weight(n, weight_key));
+                        let weight_b = node_map.get(neighbor_b).map_or(1.0, |n| node_weight(n, weight_key));
+                        weight_b.partial_cmp(&weight_a).unwrap_or(Ordering::Equal)
+                    });
+                }
+            }
+
+            for edge in candidates {
+                let neighbor = other_endpoint(edge, &current_node);
 
                 if !visited_nodes.contains(neighbor) && visited_nodes.len() < max_nodes {
                     visited_edges.insert(edge.id.clone());
-                    queue.push_back(neighbor.clone());
+                    queue.push_back(neighbor.to_string());
                 }
             }
         }
     }
 
-    let sampled_nodes: Vec<GraphNode> = visited_nodes
-        .iter()
-        .filter_map(|id| node_map.get(id.as_str()).map(|&n| n.clone()))
-        .collect();
+    (visited_nodes, visited_edges)
+}
 
-    let sampled_edges: Vec<GraphEdge> = edges
-        .iter()
-        .filter(|e| visited_edges.contains(&e.id))
-        .cloned()
-        .collect();
+/// Community detection via label propagation: each node starts in its own
+/// community and repeatedly adopts the most common label among its neighbors
+/// until labels stabilize or `max_iterations` passes have run.
+pub fn label_propagation(nodes: &[GraphNode], edges: &[GraphEdge], max_iterations: usize) -> CommunityDetectionResult {
+    let mut rng = rand::thread_rng();
+    let adjacency = build_adjacency(edges);
 
-    SampleResult {
-        sampled_nodes,
-        sampled_edges,
+    let mut labels: HashMap<String, usize> =
+        nodes.iter().enumerate().map(|(i, n)| (n.id.clone(), i)).collect();
+    let mut order: Vec<String> = nodes.iter().map(|n| n.id.clone()).collect();
+
+    for _ in 0..max_iterations {
+        order.shuffle(&mut rng);
+        let mut changed = false;
+
+        for node_id in &order {
+            let neighbor_edges = match adjacency.get(node_id) {
+                Some(e) if !e.is_empty() => e,
+                _ => continue,
+            };
+
+            let mut label_counts: HashMap<usize, usize> = HashMap::new();
+            for edge in neighbor_edges {
+                let neighbor = other_endpoint(edge, node_id);
+                if let Some(&label) = labels.get(neighbor) {
+                    *label_counts.entry(label).or_insert(0) += 1;
+                }
+            }
+
+            if label_counts.is_empty() {
+                continue;
+            }
+
+            let max_count = *label_counts.values().max().unwrap();
+            let top_labels: Vec<usize> = label_counts
+                .into_iter()
+                .filter(|&(_, count)| count == max_count)
+                .map(|(label, _)| label)
+                .collect();
+
+            let current_label = labels[node_id];
+            // Keep the current label if it's already tied for the max, to reduce oscillation
+            let new_label = if top_labels.contains(&current_label) {
+                current_label
+            } else {
+                top_labels[rng.gen_range(0..top_labels.len())]
+            };
+
+            if new_label != current_label {
+                labels.insert(node_id.clone(), new_label);
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let community_count = labels.values().collect::<HashSet<_>>().len();
+
+    CommunityDetectionResult {
+        communities: labels,
+        community_count,
     }
 }
 
@@ -212,26 +558,112 @@ mod tests {
     #[test]
     fn test_random_sample() {
         let (nodes, edges) = create_test_graph();
-        let result = random_sample(&nodes, &edges, 5);
+        let result = random_sample(&nodes, &edges, 5, None);
+
+        assert_eq!(result.sampled_nodes.len(), 5);
+    }
+
+    #[test]
+    fn test_weighted_random_sample() {
+        let (mut nodes, edges) = create_test_graph();
+        nodes[0].properties.insert("weight".to_string(), serde_json::json!(1000.0));
+
+        let result = random_sample(&nodes, &edges, 5, Some("weight"));
 
         assert_eq!(result.sampled_nodes.len(), 5);
+        assert!(result.sampled_nodes.iter().any(|n| n.id == "n0"));
     }
 
     #[test]
     fn test_random_walk() {
         let (nodes, edges) = create_test_graph();
-        let result = random_walk_sample(&nodes, &edges, "n0", 5, 3);
+        let result = random_walk_sample(&nodes, &edges, "n0", 5, 3, 1);
+
+        assert!(!result.sampled_nodes.is_empty());
+        assert!(result.sampled_nodes.iter().any(|n| n.id == "n0"));
+    }
+
+    #[test]
+    fn test_random_walk_parallel() {
+        let (nodes, edges) = create_test_graph();
+        let result = random_walk_sample(&nodes, &edges, "n0", 5, 20, 0);
 
         assert!(!result.sampled_nodes.is_empty());
         assert!(result.sampled_nodes.iter().any(|n| n.id == "n0"));
     }
 
+    #[test]
+    fn test_biased_random_walk() {
+        let (nodes, edges) = create_test_graph();
+        let result = biased_random_walk_sample(&nodes, &edges, "n0", 5, 3, 1.0, 1.0, None);
+
+        assert!(!result.sampled_nodes.is_empty());
+        assert!(result.sampled_nodes.iter().any(|n| n.id == "n0"));
+    }
+
+    #[test]
+    fn test_biased_random_walk_zero_weight_falls_back_to_uniform() {
+        let (nodes, mut edges) = create_test_graph();
+        for edge in &mut edges {
+            edge.properties.insert("weight".to_string(), serde_json::json!(0.0));
+        }
+
+        let result = biased_random_walk_sample(&nodes, &edges, "n0", 5, 3, 1.0, 1.0, Some("weight"));
+
+        assert!(!result.sampled_nodes.is_empty());
+    }
+
     #[test]
     fn test_frontier_sample() {
         let (nodes, edges) = create_test_graph();
-        let result = frontier_sample(&nodes, &edges, &["n0".to_string()], 5);
+        let result = frontier_sample(&nodes, &edges, &["n0".to_string()], 5, None, 1);
+
+        assert!(result.sampled_nodes.len() <= 5);
+        assert!(result.sampled_nodes.iter().any(|n| n.id == "n0"));
+    }
+
+    #[test]
+    fn test_frontier_sample_multi_seed_parallel() {
+        let (nodes, edges) = create_test_graph();
+        let result = frontier_sample(
+            &nodes,
+            &edges,
+            &["n0".to_string(), "n8".to_string()],
+            8,
+            None,
+            0,
+        );
+
+        assert!(result.sampled_nodes.len() <= 8);
+        // Seed nodes are always kept by the trim step, regardless of hash iteration order.
+        assert!(result.sampled_nodes.iter().any(|n| n.id == "n0"));
+        assert!(result.sampled_nodes.iter().any(|n| n.id == "n8"));
+
+        let sampled_ids: HashSet<&str> = result.sampled_nodes.iter().map(|n| n.id.as_str()).collect();
+        for edge in &result.sampled_edges {
+            assert!(sampled_ids.contains(edge.source.as_str()));
+            assert!(sampled_ids.contains(edge.target.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_weighted_frontier_sample() {
+        let (mut nodes, edges) = create_test_graph();
+        nodes[6].properties.insert("weight".to_string(), serde_json::json!(1000.0));
+
+        let result = frontier_sample(&nodes, &edges, &["n0".to_string()], 5, Some("weight"), 1);
 
         assert!(result.sampled_nodes.len() <= 5);
         assert!(result.sampled_nodes.iter().any(|n| n.id == "n0"));
     }
+
+    #[test]
+    fn test_label_propagation() {
+        let (nodes, edges) = create_test_graph();
+        let result = label_propagation(&nodes, &edges, 20);
+
+        assert_eq!(result.communities.len(), nodes.len());
+        assert!(result.community_count >= 1);
+        assert!(result.community_count <= nodes.len());
+    }
 }
@@ -0,0 +1,218 @@
+//! HTTP/WebSocket transport
+//!
+//! Exposes the same `dispatch::handle_request` used by the stdio loop over
+//! HTTP, so consumers that would rather talk to a long-lived service than
+//! spawn a child process can `POST /rpc`, or open `/ws` for a persistent
+//! streaming connection. Over `POST /rpc`, sample results are streamed to the
+//! client as they're serialized rather than buffered in memory, since
+//! `sampledNodes`/`sampledEdges` can be huge for large graphs. Over `/ws`, each
+//! response is serialized in full and sent as a single text message.
+
+use crate::dispatch;
+use crate::protocol::{ErrorResponse, GraphEdge, GraphNode, Request, SampleResult};
+use bytes::Bytes;
+use futures_util::stream::Stream;
+use futures_util::{SinkExt, StreamExt};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request as HttpRequest, Response, Server, StatusCode};
+use hyper_tungstenite::tungstenite::Message;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Run the HTTP/WebSocket server until it errors
+pub async fn serve(addr: SocketAddr) {
+    let make_svc = make_service_fn(|_conn| async { Ok::<_, Infallible>(service_fn(handle)) });
+
+    eprintln!("GraphScope Accelerator listening on http://{}", addr);
+
+    if let Err(e) = Server::bind(&addr).serve(make_svc).await {
+        eprintln!("HTTP server error: {}", e);
+    }
+}
+
+async fn handle(req: HttpRequest<Body>) -> Result<Response<Body>, Infallible> {
+    if hyper_tungstenite::is_upgrade_request(&req) {
+        return Ok(handle_websocket_upgrade(req));
+    }
+
+    match (req.method(), req.uri().path()) {
+        (&Method::POST, "/rpc") => Ok(handle_rpc(req).await),
+        _ => {
+            let mut response = Response::new(Body::from("not found"));
+            *response.status_mut() = StatusCode::NOT_FOUND;
+            Ok(response)
+        }
+    }
+}
+
+async fn handle_rpc(req: HttpRequest<Body>) -> Response<Body> {
+    let body = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(b) => b,
+        Err(e) => return error_response(0, format!("Failed to read request body: {}", e)),
+    };
+
+    let rpc_request: Request = match serde_json::from_slice(&body) {
+        Ok(r) => r,
+        Err(e) => return error_response(0, format!("Parse error: {}", e)),
+    };
+
+    dispatch_response(rpc_request)
+}
+
+fn dispatch_response(request: Request) -> Response<Body> {
+    let id = request.id;
+
+    if let Some(result) = dispatch::compute_sample_result(&request) {
+        return match result {
+            Ok(sample) => json_stream_response(SampleResultStream::new(id, sample)),
+            Err(e) => error_response(id, e),
+        };
+    }
+
+    match dispatch::handle_request(&request) {
+        Ok(json) => Response::builder()
+            .header("content-type", "application/json")
+            .body(Body::from(json))
+            .unwrap_or_else(|_| error_response(id, "failed to build response".to_string())),
+        Err(e) => error_response(id, e),
+    }
+}
+
+fn json_stream_response(stream: SampleResultStream) -> Response<Body> {
+    Response::builder()
+        .header("content-type", "application/json")
+        .body(Body::wrap_stream(stream))
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
+
+fn error_response(id: u64, message: String) -> Response<Body> {
+    let response = ErrorResponse::internal_error(id, message);
+    let body = serde_json::to_string(&response).unwrap_or_default();
+    Response::builder()
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
+
+fn handle_websocket_upgrade(req: HttpRequest<Body>) -> Response<Body> {
+    match hyper_tungstenite::upgrade(req, None) {
+        Ok((response, websocket)) => {
+            tokio::spawn(async move {
+                if let Err(e) = serve_websocket(websocket).await {
+                    eprintln!("WebSocket error: {}", e);
+                }
+            });
+            response
+        }
+        Err(e) => {
+            let mut response = Response::new(Body::from(format!("WebSocket upgrade failed: {}", e)));
+            *response.status_mut() = StatusCode::BAD_REQUEST;
+            response
+        }
+    }
+}
+
+async fn serve_websocket(
+    websocket: hyper_tungstenite::HyperWebsocket,
+) -> Result<(), hyper_tungstenite::tungstenite::Error> {
+    let mut websocket = websocket.await?;
+
+    while let Some(message) = websocket.next().await {
+        let Message::Text(text) = message? else {
+            continue;
+        };
+
+        let rpc_request: Request = match serde_json::from_str(&text) {
+            Ok(r) => r,
+            Err(e) => {
+                let response = ErrorResponse::new(0, -32700, format!("Parse error: {}", e));
+                let body = serde_json::to_string(&response).unwrap_or_default();
+                websocket.send(Message::Text(body)).await?;
+                continue;
+            }
+        };
+
+        let json = match dispatch::handle_request(&rpc_request) {
+            Ok(json) => json,
+            Err(e) => {
+                let response = ErrorResponse::internal_error(rpc_request.id, e);
+                serde_json::to_string(&response).unwrap_or_default()
+            }
+        };
+        websocket.send(Message::Text(json)).await?;
+    }
+
+    Ok(())
+}
+
+/// Serializes a `SampleResult` incrementally as a JSON-RPC success response, so
+/// large results never need to be buffered in memory all at once.
+///
+/// `hyper::Body::wrap_stream` requires the stream to be `Send + Unpin`; this is
+/// satisfied since every field here is an owned, plain iterator or enum.
+struct SampleResultStream {
+    id: u64,
+    nodes: std::vec::IntoIter<GraphNode>,
+    edges: std::vec::IntoIter<GraphEdge>,
+    state: StreamState,
+}
+
+enum StreamState {
+    Header,
+    Nodes { first: bool },
+    Edges { first: bool },
+    Done,
+}
+
+impl SampleResultStream {
+    fn new(id: u64, result: SampleResult) -> Self {
+        Self {
+            id,
+            nodes: result.sampled_nodes.into_iter(),
+            edges: result.sampled_edges.into_iter(),
+            state: StreamState::Header,
+        }
+    }
+}
+
+impl Stream for SampleResultStream {
+    type Item = Result<Bytes, Infallible>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let chunk = match &mut this.state {
+            StreamState::Header => {
+                this.state = StreamState::Nodes { first: true };
+                format!(r#"{{"jsonrpc":"2.0","id":{},"result":{{"sampledNodes":["#, this.id)
+            }
+            StreamState::Nodes { first } => match this.nodes.next() {
+                Some(node) => {
+                    let prefix = if *first { "" } else { "," };
+                    *first = false;
+                    format!("{}{}", prefix, serde_json::to_string(&node).unwrap_or_default())
+                }
+                None => {
+                    this.state = StreamState::Edges { first: true };
+                    r#"],"sampledEdges":["#.to_string()
+                }
+            },
+            StreamState::Edges { first } => match this.edges.next() {
+                Some(edge) => {
+                    let prefix = if *first { "" } else { "," };
+                    *first = false;
+                    format!("{}{}", prefix, serde_json::to_string(&edge).unwrap_or_default())
+                }
+                None => {
+                    this.state = StreamState::Done;
+                    "]}}".to_string()
+                }
+            },
+            StreamState::Done => return Poll::Ready(None),
+        };
+
+        Poll::Ready(Some(Ok(Bytes::from(chunk))))
+    }
+}